@@ -0,0 +1,169 @@
+//! PKCS#10 Certification Request (CSR) objects
+//!
+//! Based on [RFC2986](https://tools.ietf.org/html/rfc2986)
+//!
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+
+use der_parser::ber::BitStringObject;
+use der_parser::der::*;
+use der_parser::error::BerError;
+use der_parser::oid::Oid;
+use nom::Err;
+
+use crate::error::X509Error;
+use crate::extensions::{parse_extension_sequence, ParsedExtension};
+use crate::objects::OID_PKCS9_EXTENSION_REQUEST;
+use crate::x509::{
+    parse_algorithm_identifier, parse_subject_public_key_info, parse_x509_name,
+    AlgorithmIdentifier, SubjectPublicKeyInfo, X509Name,
+};
+
+/// An X.509 v1 Certification Request, as specified by PKCS#10 (RFC2986).
+///
+/// ```text
+/// CertificationRequest ::= SEQUENCE {
+///     certificationRequestInfo CertificationRequestInfo,
+///     signatureAlgorithm       AlgorithmIdentifier,
+///     signature                BIT STRING
+/// }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct CertificationRequest<'a> {
+    pub certification_request_info: CertificationRequestInfo<'a>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature_value: BitStringObject<'a>,
+}
+
+impl<'a> CertificationRequest<'a> {
+    /// Get the certification request subject.
+    #[inline]
+    pub fn subject(&self) -> &X509Name {
+        &self.certification_request_info.subject
+    }
+
+    /// Get the certification request public key information.
+    #[inline]
+    pub fn public_key(&self) -> &SubjectPublicKeyInfo {
+        &self.certification_request_info.subject_pki
+    }
+
+    /// Get the extensions requested by the `extensionRequest` attribute, if any.
+    ///
+    /// Extensions are parsed and decoded using the same machinery as certificate
+    /// extensions, so the returned values can be matched the same way.
+    pub fn requested_extensions(&self) -> Option<impl Iterator<Item = &ParsedExtension>> {
+        self.certification_request_info
+            .attributes
+            .get(&OID_PKCS9_EXTENSION_REQUEST)
+            .map(|attr| attr.parsed_extensions.values())
+    }
+}
+
+/// ```text
+/// CertificationRequestInfo ::= SEQUENCE {
+///     version       INTEGER { v1(0) } (v1,...),
+///     subject       Name,
+///     subjectPKInfo SubjectPublicKeyInfo{{ PKInfoAlgorithms }},
+///     attributes    [0] Attributes{{ CRIAttributes }}
+/// }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct CertificationRequestInfo<'a> {
+    pub version: u32,
+    pub subject: X509Name<'a>,
+    pub subject_pki: SubjectPublicKeyInfo<'a>,
+    pub attributes: BTreeMap<Oid<'a>, X509CriAttribute<'a>>,
+    pub(crate) raw: &'a [u8],
+}
+
+/// A PKCS#10 attribute.
+///
+/// The `extensionRequest` attribute (OID 1.2.840.113549.1.9.14) is of particular
+/// interest to CA software: its value is the same `Extensions` SEQUENCE found in a
+/// certificate, so it is decoded into `ParsedExtension` values the same way.
+#[derive(Debug, PartialEq)]
+pub struct X509CriAttribute<'a> {
+    pub oid: Oid<'a>,
+    pub value: &'a [u8],
+    pub(crate) parsed_extensions: BTreeMap<Oid<'a>, ParsedExtension<'a>>,
+}
+
+// Attribute ::= SEQUENCE { type OBJECT IDENTIFIER, values SET OF AttributeValue }
+fn parse_cri_attribute(i: &[u8]) -> Result<(&[u8], X509CriAttribute), Err<BerError>> {
+    parse_der_sequence_defined_g(|content, _| {
+        let (rem, oid) = parse_der_oid(content)?;
+        let oid = oid.as_oid_val()?;
+        let (rem, values) = parse_der_set(rem)?;
+        let value = values.as_slice()?;
+        let parsed_extensions = if oid == OID_PKCS9_EXTENSION_REQUEST {
+            // the attribute value is a SET containing a single Extensions SEQUENCE
+            values
+                .as_set()
+                .ok()
+                .and_then(|set| set.first())
+                .and_then(|exts| exts.as_slice().ok())
+                .and_then(|exts| parse_extension_sequence(exts).ok())
+                .map(|(_, exts)| exts)
+                .unwrap_or_default()
+        } else {
+            BTreeMap::new()
+        };
+        let attr = X509CriAttribute {
+            oid,
+            value,
+            parsed_extensions,
+        };
+        Ok((rem, attr))
+    })(i)
+}
+
+pub(crate) fn parse_certification_request_info(
+    i: &[u8],
+) -> Result<(&[u8], CertificationRequestInfo), Err<BerError>> {
+    let start_i = i;
+    parse_der_sequence_defined_g(move |content, _| {
+        let (i, version_obj) = parse_der_integer(content)?;
+        let version = version_obj.as_u32()?;
+        let (i, subject) = parse_x509_name(i)?;
+        let (i, subject_pki) = parse_subject_public_key_info(i)?;
+        let (i, attributes) = parse_der_tagged_explicit_g(0, |c, _| {
+            let mut attributes = BTreeMap::new();
+            let mut rem = c;
+            while !rem.is_empty() {
+                let (r, attr) = parse_cri_attribute(rem)?;
+                attributes.insert(attr.oid.clone(), attr);
+                rem = r;
+            }
+            Ok((rem, attributes))
+        })(i)?;
+        let info = CertificationRequestInfo {
+            version,
+            subject,
+            subject_pki,
+            attributes,
+            raw: &start_i[..start_i.len() - i.len()],
+        };
+        Ok((i, info))
+    })(i)
+}
+
+/// Parse a DER-encoded PKCS#10 Certification Signing Request (CSR)
+pub fn parse_x509_csr(i: &[u8]) -> Result<(&[u8], CertificationRequest), X509Error> {
+    parse_der_sequence_defined_g(|content, _| {
+        let (i, certification_request_info) = parse_certification_request_info(content)?;
+        let (i, signature_algorithm) = parse_algorithm_identifier(i)?;
+        let (i, signature_value) = parse_der_bitstring(i)?;
+        let signature_value = signature_value.as_bitstring()?.to_owned();
+        let csr = CertificationRequest {
+            certification_request_info,
+            signature_algorithm,
+            signature_value,
+        };
+        Ok((i, csr))
+    })(i)
+    .map_err(|_| X509Error::InvalidCertificate)
+}
@@ -0,0 +1,179 @@
+//! Ergonomic, typed accessors over `KeyUsage` and `GeneralName`
+//!
+//! `key_usage()` and `subject_alternative_name()` expose the raw parsed extension
+//! types; this module adds a bit-level API over `KeyUsage` and a typed `GeneralName`
+//! enum so callers can do host/IP matching and usage checks without manual bit
+//! masking or raw-byte inspection.
+
+#[cfg(feature = "std")]
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::net::IpAddr;
+#[cfg(not(feature = "std"))]
+use core::net::IpAddr;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::extensions::KeyUsage;
+use crate::objects::OID_EXT_SAN;
+use crate::x509::{TbsCertificate, X509Name};
+
+macro_rules! key_usage_bit {
+    ($method:ident, $doc:expr, $bit:expr) => {
+        #[doc = $doc]
+        #[inline]
+        pub fn $method(&self) -> bool {
+            self.flags & (1 << $bit) != 0
+        }
+    };
+}
+
+impl KeyUsage {
+    key_usage_bit!(digital_signature, "The `digitalSignature` bit is set.", 0);
+    key_usage_bit!(non_repudiation, "The `nonRepudiation` bit is set.", 1);
+    key_usage_bit!(key_encipherment, "The `keyEncipherment` bit is set.", 2);
+    key_usage_bit!(data_encipherment, "The `dataEncipherment` bit is set.", 3);
+    key_usage_bit!(key_agreement, "The `keyAgreement` bit is set.", 4);
+    key_usage_bit!(key_cert_sign, "The `keyCertSign` bit is set.", 5);
+    key_usage_bit!(crl_sign, "The `cRLSign` bit is set.", 6);
+    key_usage_bit!(encipher_only, "The `encipherOnly` bit is set.", 7);
+    key_usage_bit!(decipher_only, "The `decipherOnly` bit is set.", 8);
+
+    /// Iterate over the names of the bits that are set, in RFC5280 declaration order.
+    pub fn iter_set_bits(&self) -> impl Iterator<Item = &'static str> + '_ {
+        KEY_USAGE_BIT_NAMES
+            .iter()
+            .filter(move |(bit, _)| self.flags & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+    }
+}
+
+const KEY_USAGE_BIT_NAMES: &[(u8, &str)] = &[
+    (0, "digitalSignature"),
+    (1, "nonRepudiation"),
+    (2, "keyEncipherment"),
+    (3, "dataEncipherment"),
+    (4, "keyAgreement"),
+    (5, "keyCertSign"),
+    (6, "cRLSign"),
+    (7, "encipherOnly"),
+    (8, "decipherOnly"),
+];
+
+impl fmt::Display for KeyUsage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut names = self.iter_set_bits();
+        if let Some(first) = names.next() {
+            write!(f, "{}", first)?;
+            for name in names {
+                write!(f, ", {}", name)?;
+            }
+            Ok(())
+        } else {
+            write!(f, "none")
+        }
+    }
+}
+
+/// A `GeneralName`, as used in `subjectAltName`, `issuerAltName`, and name constraints
+/// (RFC5280 section 4.2.1.6).
+#[derive(Debug, PartialEq)]
+pub enum GeneralName<'a> {
+    OtherName(&'a [u8]),
+    Rfc822Name(&'a str),
+    DnsName(&'a str),
+    X400Address(&'a [u8]),
+    DirectoryName(X509Name<'a>),
+    EdiPartyName(&'a [u8]),
+    UniformResourceIdentifier(&'a str),
+    IpAddress(IpAddr),
+    RegisteredId(der_parser::oid::Oid<'a>),
+}
+
+impl<'a> GeneralName<'a> {
+    /// Decode the `iPAddress` general name variant from its raw DER `OCTET STRING`
+    /// content: 4 bytes for an IPv4 address, 16 for IPv6.
+    pub(crate) fn ip_address_from_bytes(bytes: &[u8]) -> Option<IpAddr> {
+        match bytes.len() {
+            4 => {
+                let octets: [u8; 4] = bytes.try_into().ok()?;
+                Some(IpAddr::from(octets))
+            }
+            16 => {
+                let octets: [u8; 16] = bytes.try_into().ok()?;
+                Some(IpAddr::from(octets))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Read one DER/BER tag-length-value from the front of `i`, returning
+/// `(tag, content, remainder)`. `GeneralName` entries are `[n] IMPLICIT ...`, so the
+/// tag byte alone (masked to its low 5 bits) identifies which CHOICE variant follows;
+/// this only needs to walk the length encoding, not interpret the tag's class/form.
+fn read_tlv(i: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let &tag = i.first()?;
+    let &first_len = i.get(1)?;
+    let (len, header_len) = if first_len & 0x80 == 0 {
+        (first_len as usize, 2)
+    } else {
+        let num_octets = (first_len & 0x7f) as usize;
+        let len_bytes = i.get(2..2 + num_octets)?;
+        let len = len_bytes
+            .iter()
+            .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+        (len, 2 + num_octets)
+    };
+    let content = i.get(header_len..header_len + len)?;
+    let rest = i.get(header_len + len..)?;
+    Some((tag, content, rest))
+}
+
+/// Decode a `GeneralNames ::= SEQUENCE OF GeneralName` content (the inner bytes of
+/// the outer `SEQUENCE`, not including its own tag/length) into typed `GeneralName`s.
+///
+/// Entries this crate does not know how to interpret as one of the listed variants
+/// (e.g. a malformed `directoryName`) are skipped rather than aborting the whole scan.
+pub fn parse_general_names(mut i: &[u8]) -> Vec<GeneralName> {
+    let mut result = Vec::new();
+    while let Some((tag, content, rest)) = read_tlv(i) {
+        let general_name = match tag & 0x1f {
+            0 => Some(GeneralName::OtherName(content)),
+            1 => core::str::from_utf8(content).ok().map(GeneralName::Rfc822Name),
+            2 => core::str::from_utf8(content).ok().map(GeneralName::DnsName),
+            3 => Some(GeneralName::X400Address(content)),
+            4 => crate::x509::parse_x509_name(content)
+                .ok()
+                .map(|(_, name)| GeneralName::DirectoryName(name)),
+            5 => Some(GeneralName::EdiPartyName(content)),
+            6 => core::str::from_utf8(content)
+                .ok()
+                .map(GeneralName::UniformResourceIdentifier),
+            7 => GeneralName::ip_address_from_bytes(content).map(GeneralName::IpAddress),
+            8 => Some(GeneralName::RegisteredId(der_parser::oid::Oid::new(
+                content.into(),
+            ))),
+            _ => None,
+        };
+        result.extend(general_name);
+        i = rest;
+    }
+    result
+}
+
+impl<'a> TbsCertificate<'a> {
+    /// Get the certificate's `subjectAltName` extension, decoded into typed
+    /// [`GeneralName`]s so callers can match hostnames/IPs without raw-byte
+    /// inspection. This complements [`TbsCertificate::subject_alternative_name`],
+    /// which returns the extension in its less-structured, parser-native form.
+    pub fn subject_alternative_names(&self) -> Option<(bool, Vec<GeneralName>)> {
+        let ext = self.extensions().get(&OID_EXT_SAN)?;
+        let (_, content, _) = read_tlv(ext.value)?;
+        Some((ext.critical, parse_general_names(content)))
+    }
+}
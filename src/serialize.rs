@@ -0,0 +1,266 @@
+//! Re-encoding parsed structures back to DER/PEM
+//!
+//! All of the types in `x509` are zero-copy read-only views over a DER buffer. This
+//! module adds the inverse operation: turning a (possibly freshly built)
+//! `TbsCertificate` back into bytes, so the crate can also be used to mint
+//! certificates instead of only parsing them. [`TbsCertificateBuilder`] assembles a
+//! `TBSCertificate` (optionally with extensions) from its components and, given a
+//! signing closure, can sign and assemble the complete certificate in one step.
+
+use der_parser::der::{DerObject, Tag};
+use der_parser::oid::Oid;
+
+use crate::error::X509Error;
+use crate::x509::{
+    AlgorithmIdentifier, SubjectPublicKeyInfo, TbsCertificate, X509Certificate, X509Extension,
+    X509Name,
+};
+
+/// Re-encode a parsed structure back into its DER representation.
+///
+/// Implemented for the structures that are plain views over a DER `SEQUENCE`
+/// (`X509Name`, `AlgorithmIdentifier`, `SubjectPublicKeyInfo`, `TbsCertificate`,
+/// `X509Certificate`, `X509Extension`). Encoding never fails for values that were
+/// produced by the parser, since they are already valid DER; it can fail for values
+/// assembled by hand through [`TbsCertificateBuilder`] if a component is malformed.
+pub trait ToDer {
+    fn to_der(&self) -> Result<Vec<u8>, X509Error>;
+
+    /// Re-encode to PEM, using the given label (e.g. `"CERTIFICATE"`, `"CERTIFICATE REQUEST"`).
+    fn to_pem(&self, label: &str) -> Result<String, X509Error> {
+        let der = self.to_der()?;
+        Ok(pem::encode(&pem::Pem {
+            tag: label.to_string(),
+            contents: der,
+        }))
+    }
+}
+
+impl<'a> ToDer for X509Name<'a> {
+    fn to_der(&self) -> Result<Vec<u8>, X509Error> {
+        Ok(self.as_raw().to_vec())
+    }
+}
+
+impl<'a> ToDer for AlgorithmIdentifier<'a> {
+    fn to_der(&self) -> Result<Vec<u8>, X509Error> {
+        let mut seq = Vec::new();
+        seq.extend_from_slice(&oid_to_der(&self.algorithm));
+        seq.extend_from_slice(&self.parameters.to_vec().map_err(|_| X509Error::InvalidAlgorithmIdentifier)?);
+        Ok(der_sequence(&seq))
+    }
+}
+
+impl<'a> ToDer for SubjectPublicKeyInfo<'a> {
+    fn to_der(&self) -> Result<Vec<u8>, X509Error> {
+        let mut seq = self.algorithm.to_der()?;
+        seq.extend_from_slice(&bitstring_to_der(self.subject_public_key.data));
+        Ok(der_sequence(&seq))
+    }
+}
+
+impl<'a> ToDer for TbsCertificate<'a> {
+    fn to_der(&self) -> Result<Vec<u8>, X509Error> {
+        // TbsCertificate is always built from a DER SEQUENCE by the parser, so the
+        // raw bytes captured at parse time are already the canonical re-encoding.
+        Ok(self.raw.to_vec())
+    }
+}
+
+impl<'a> ToDer for X509Extension<'a> {
+    fn to_der(&self) -> Result<Vec<u8>, X509Error> {
+        let mut seq = oid_to_der(&self.oid);
+        if self.critical {
+            seq.extend_from_slice(&der_tagged(Tag::Boolean as u8, &[0xff]));
+        }
+        seq.extend_from_slice(&octet_string_to_der(self.value));
+        Ok(der_sequence(&seq))
+    }
+}
+
+impl<'a> ToDer for X509Certificate<'a> {
+    fn to_der(&self) -> Result<Vec<u8>, X509Error> {
+        let mut seq = self.tbs_certificate.to_der()?;
+        seq.extend_from_slice(&self.signature_algorithm.to_der()?);
+        seq.extend_from_slice(&bitstring_to_der(self.signature_value.data));
+        Ok(der_sequence(&seq))
+    }
+}
+
+/// Minimal DER length/tag helpers, enough to re-assemble a SEQUENCE of already-encoded
+/// children. Full general-purpose encoding (indefinite lengths, multi-byte tags, etc.)
+/// is out of scope: every value this module writes comes from a value the parser
+/// already validated, or from the builder below which only ever produces
+/// single-byte-tag, definite-length DER.
+fn der_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        vec![len as u8]
+    } else {
+        let bytes = len.to_be_bytes();
+        let first_nonzero = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1);
+        let mut out = vec![0x80 | (bytes.len() - first_nonzero) as u8];
+        out.extend_from_slice(&bytes[first_nonzero..]);
+        out
+    }
+}
+
+fn der_tagged(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    out.extend_from_slice(&der_length(content.len()));
+    out.extend_from_slice(content);
+    out
+}
+
+fn der_sequence(content: &[u8]) -> Vec<u8> {
+    der_tagged(Tag::Sequence as u8 | 0x20, content)
+}
+
+fn bitstring_to_der(data: &[u8]) -> Vec<u8> {
+    let mut content = Vec::with_capacity(data.len() + 1);
+    content.push(0); // no unused bits
+    content.extend_from_slice(data);
+    der_tagged(Tag::BitString as u8, &content)
+}
+
+fn oid_to_der(oid: &Oid) -> Vec<u8> {
+    der_tagged(Tag::Oid as u8, &oid.as_bytes())
+}
+
+fn octet_string_to_der(data: &[u8]) -> Vec<u8> {
+    der_tagged(Tag::OctetString as u8, data)
+}
+
+/// Encode a non-empty list of extensions as a `[3] EXPLICIT Extensions` field (RFC5280
+/// section 4.1), using each extension's already-encoded `value` bytes rather than
+/// re-deriving DER from its decoded `ParsedExtension` (which would need an encoder for
+/// every extension type this crate knows how to parse). Returns an empty `Vec` for an
+/// empty list, since the field is `OPTIONAL` and a v3 certificate with no extensions
+/// should omit it entirely.
+fn extensions_to_der(extensions: &[X509Extension]) -> Result<Vec<u8>, X509Error> {
+    if extensions.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut seq = Vec::new();
+    for extension in extensions {
+        seq.extend_from_slice(&extension.to_der()?);
+    }
+    Ok(der_tagged(0xa3, &der_sequence(&seq)))
+}
+
+/// Encode `value` as a minimal, unsigned DER `INTEGER`: strip leading `0x00` octets
+/// (down to a single byte for a zero value), then prepend a `0x00` sign byte if the
+/// most significant bit of what remains is set, so the value is never mistaken for
+/// a negative number.
+fn integer_to_der(value: &[u8]) -> Vec<u8> {
+    let first_nonzero = value.iter().position(|&b| b != 0).unwrap_or(value.len() - 1);
+    let trimmed = &value[first_nonzero..];
+    let content: Vec<u8> = if trimmed[0] & 0x80 != 0 {
+        core::iter::once(0).chain(trimmed.iter().copied()).collect()
+    } else {
+        trimmed.to_vec()
+    };
+    der_tagged(Tag::Integer as u8, &content)
+}
+
+/// Builds a [`TbsCertificate`] from scratch, for minting new certificates instead of
+/// only parsing existing ones.
+///
+/// This mirrors the common CA workflow: set the fields that make up the "to be
+/// signed" part (including, optionally, extensions via [`Self::extensions`]), call
+/// [`Self::build`] to get just the encoded `TBSCertificate`, or
+/// [`Self::build_and_sign`] to sign it and assemble the complete, signed certificate
+/// in one step (see [`crate::verify`] for the matching signature verification).
+pub struct TbsCertificateBuilder<'a> {
+    serial: u64,
+    signature: AlgorithmIdentifier<'a>,
+    issuer: &'a [u8],
+    not_before_der: DerObject<'a>,
+    not_after_der: DerObject<'a>,
+    subject: &'a [u8],
+    subject_pki: SubjectPublicKeyInfo<'a>,
+    extensions: Vec<X509Extension<'a>>,
+}
+
+impl<'a> TbsCertificateBuilder<'a> {
+    pub fn new(
+        serial: u64,
+        signature: AlgorithmIdentifier<'a>,
+        issuer: &'a X509Name<'a>,
+        not_before_der: DerObject<'a>,
+        not_after_der: DerObject<'a>,
+        subject: &'a X509Name<'a>,
+        subject_pki: SubjectPublicKeyInfo<'a>,
+    ) -> Self {
+        TbsCertificateBuilder {
+            serial,
+            signature,
+            issuer: issuer.as_raw(),
+            not_before_der,
+            not_after_der,
+            subject: subject.as_raw(),
+            subject_pki,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Set the `extensions` field of the certificate being built. Defaults to empty
+    /// (no `extensions` field at all, as RFC5280 permits), if never called.
+    pub fn extensions(mut self, extensions: Vec<X509Extension<'a>>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Assemble the DER encoding of the `TBSCertificate` SEQUENCE described by this
+    /// builder.
+    pub fn build(self) -> Result<Vec<u8>, X509Error> {
+        let mut content = Vec::new();
+        content.extend_from_slice(&der_tagged(0xa0, &der_tagged(0x02, &[2]))); // version v3
+        content.extend_from_slice(&integer_to_der(&self.serial.to_be_bytes()));
+        content.extend_from_slice(&self.signature.to_der()?);
+        content.extend_from_slice(self.issuer);
+        let validity = der_sequence(
+            &[
+                self.not_before_der
+                    .to_vec()
+                    .map_err(|_| X509Error::InvalidDate)?,
+                self.not_after_der
+                    .to_vec()
+                    .map_err(|_| X509Error::InvalidDate)?,
+            ]
+            .concat(),
+        );
+        content.extend_from_slice(&validity);
+        content.extend_from_slice(self.subject);
+        content.extend_from_slice(&self.subject_pki.to_der()?);
+        content.extend_from_slice(&extensions_to_der(&self.extensions)?);
+        Ok(der_sequence(&content))
+    }
+
+    /// Sign the assembled `TBSCertificate` and assemble the complete, signed DER
+    /// encoding of an `X509Certificate`.
+    ///
+    /// `sign` receives the raw TBS bytes and must return the raw signature bytes
+    /// produced over them by the caller's private key, encoded the way this
+    /// builder's `signature` algorithm identifier calls for (e.g. a PKCS#1 v1.5
+    /// signature for an RSA algorithm, or an ASN.1 `ECDSA-Sig-Value` for an ECDSA
+    /// one) -- this module has no signing key material or RNG of its own, only the
+    /// encoding logic.
+    ///
+    /// This returns the signed certificate's DER bytes rather than a typed
+    /// `X509Certificate`: every type in this module is a zero-copy view borrowed
+    /// from an external buffer, and a certificate assembled here has no such buffer
+    /// to borrow from until it is written out. Parse the returned bytes (e.g. with
+    /// `parse_x509_der`) to get a typed view of the certificate just minted.
+    pub fn build_and_sign(
+        self,
+        sign: impl FnOnce(&[u8]) -> Result<Vec<u8>, X509Error>,
+    ) -> Result<Vec<u8>, X509Error> {
+        let signature_algorithm_der = self.signature.to_der()?;
+        let tbs_der = self.build()?;
+        let signature = sign(&tbs_der)?;
+        let mut content = tbs_der;
+        content.extend_from_slice(&signature_algorithm_der);
+        content.extend_from_slice(&bitstring_to_der(&signature));
+        Ok(der_sequence(&content))
+    }
+}
@@ -0,0 +1,448 @@
+//! Trust-anchor based certificate chain validation
+//!
+//! This module implements the path validation algorithm described in
+//! [RFC5280](https://tools.ietf.org/html/rfc5280) section 6, building on top of
+//! [`X509Certificate::verify_signature`](crate::x509::X509Certificate::verify_signature).
+//! It is gated behind the `verify` feature since it needs a crypto backend to check
+//! signatures up the chain.
+
+#[cfg(feature = "std")]
+use std::net::IpAddr;
+#[cfg(not(feature = "std"))]
+use core::net::IpAddr;
+
+use chrono::{DateTime, Utc};
+
+use crate::general_name::GeneralName;
+use crate::x509::{X509Certificate, X509Name};
+
+/// The keyUsage bit (RFC5280 section 4.2.1.3) an issuing CA certificate must assert.
+const KEY_USAGE_KEY_CERT_SIGN: u16 = 1 << 5;
+
+/// A trusted root certificate, used as the anchor of a validated chain.
+pub struct TrustAnchor<'a> {
+    pub certificate: X509Certificate<'a>,
+}
+
+/// Why a single certificate in the chain failed to validate.
+#[derive(Debug, PartialEq)]
+pub enum ValidationFailure {
+    /// No candidate issuer certificate (or trust anchor) matches this certificate's issuer name.
+    NoIssuerFound,
+    /// A chain of issuers was found for this certificate, but it does not terminate
+    /// at (or immediately below) a certificate in `anchors`. A self-signed certificate
+    /// that is not itself a trust anchor falls under this, not `NoIssuerFound`.
+    NoTrustAnchor,
+    /// The signature did not verify against the candidate issuer's public key.
+    SignatureInvalid,
+    /// The evaluation time is before `notBefore` or after `notAfter`.
+    Expired,
+    /// The certificate's issuer lacks `basicConstraints CA:true`.
+    NotACa,
+    /// The certificate's issuer asserts a `pathLenConstraint` that is too small for the
+    /// number of CA certificates actually subordinate to it.
+    PathLenExceeded,
+    /// The certificate's issuer does not assert the `keyCertSign` bit of `keyUsage`.
+    KeyUsageMissingKeyCertSign,
+    /// The subject DN, or a `dNSName`/`iPAddress` entry of the `subjectAltName`, falls
+    /// outside a permitted subtree (or inside an excluded one) of a `nameConstraints`
+    /// extension on an ancestor certificate.
+    ///
+    /// `directoryName`, `dNSName`, and `iPAddress` subtrees are matched; other
+    /// `GeneralName` forms (`rfc822Name`, `uniformResourceIdentifier`, ...) in a
+    /// `nameConstraints` extension are ignored. An `iPAddress` subtree's constraint is
+    /// matched as an exact address, since the netmask RFC5280 pairs it with is not
+    /// preserved separately by [`GeneralName::IpAddress`](crate::general_name::GeneralName::IpAddress).
+    NameConstraintsViolated,
+    /// The leaf certificate's `extendedKeyUsage` is not permitted by an ancestor CA's
+    /// own `extendedKeyUsage` restriction.
+    ExtendedKeyUsageNotPermitted,
+}
+
+/// Per-certificate outcome of [`validate_chain`], in the same order as the input `chain`.
+#[derive(Debug, PartialEq)]
+pub struct CertificateValidationResult {
+    /// Index of the certificate within the `chain` slice passed to `validate_chain`.
+    pub certificate_index: usize,
+    pub failure: Option<ValidationFailure>,
+}
+
+/// Outcome of validating a certificate chain against a set of trust anchors.
+#[derive(Debug, PartialEq)]
+pub struct ChainValidationResult {
+    pub results: Vec<CertificateValidationResult>,
+}
+
+impl ChainValidationResult {
+    /// `true` if every certificate in the chain validated with no failure.
+    pub fn is_valid(&self) -> bool {
+        self.results.iter().all(|r| r.failure.is_none())
+    }
+}
+
+/// Find, among `chain` and `anchors`, the certificate whose subject matches `cert`'s issuer.
+fn find_issuer<'a, 'b>(
+    cert: &X509Certificate<'b>,
+    chain: &'a [X509Certificate<'b>],
+    anchors: &'a [TrustAnchor<'b>],
+) -> Option<&'a X509Certificate<'b>> {
+    chain
+        .iter()
+        .chain(anchors.iter().map(|a| &a.certificate))
+        .find(|candidate| candidate.subject() == cert.issuer())
+}
+
+/// Walk upward from `start` (inclusive) through issuers found in `chain`/`anchors`,
+/// stopping at the first self-signed certificate (or when no further issuer can be
+/// found). Used to apply ancestor-wide checks (name constraints, EKU nesting) without
+/// assuming any particular order for `chain`.
+fn ancestor_chain<'a, 'b>(
+    start: &'a X509Certificate<'b>,
+    chain: &'a [X509Certificate<'b>],
+    anchors: &'a [TrustAnchor<'b>],
+) -> Vec<&'a X509Certificate<'b>> {
+    let mut result = Vec::new();
+    let mut current = start;
+    let max_len = chain.len() + anchors.len() + 1;
+    loop {
+        result.push(current);
+        if result.len() >= max_len || current.subject() == current.issuer() {
+            break;
+        }
+        match find_issuer(current, chain, anchors) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    result
+}
+
+/// Certificates in `chain` that `issuer` directly issued (their `issuer` name matches
+/// `issuer`'s `subject`, and they are not `issuer` itself).
+fn certs_issued_by<'a, 'b, 'c>(
+    issuer: &'c X509Certificate<'b>,
+    chain: &'a [X509Certificate<'b>],
+) -> impl Iterator<Item = &'a X509Certificate<'b>> + 'c
+where
+    'a: 'c,
+{
+    chain
+        .iter()
+        .filter(move |c| c.issuer() == issuer.subject() && c.subject() != issuer.subject())
+}
+
+/// Is `cert` literally one of the trusted `anchors` (compared by its raw TBS bytes,
+/// since `X509Certificate` has no identity-free equality)?
+fn is_trust_anchor(cert: &X509Certificate, anchors: &[TrustAnchor]) -> bool {
+    anchors
+        .iter()
+        .any(|a| a.certificate.tbs_certificate.raw == cert.tbs_certificate.raw)
+}
+
+fn is_ca(cert: &X509Certificate) -> bool {
+    cert.tbs_certificate
+        .basic_constraints()
+        .map(|(_, bc)| bc.ca)
+        .unwrap_or(false)
+}
+
+/// The length of the longest run of CA certificates subordinate to `cert` (not
+/// counting `cert` itself), found by following `certs_issued_by` downward through
+/// `chain`. Sibling branches are not summed; leaf (non-CA) descendants do not add to
+/// the count.
+fn subordinate_ca_depth(cert: &X509Certificate, chain: &[X509Certificate]) -> u32 {
+    certs_issued_by(cert, chain)
+        .filter(|child| is_ca(child))
+        .map(|child| 1 + subordinate_ca_depth(child, chain))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Does `base`'s RDN sequence form a prefix of `name`'s RDN sequence? This is the
+/// usual interpretation of a `directoryName` name-constraints subtree: `name` is
+/// "within" `base` if every RDN of `base` appears, in order, at the start of `name`.
+fn directory_name_is_within(name: &X509Name, base: &X509Name) -> bool {
+    base.rdn_seq.len() <= name.rdn_seq.len()
+        && base
+            .rdn_seq
+            .iter()
+            .zip(name.rdn_seq.iter())
+            .all(|(b, n)| b == n)
+}
+
+/// Is `name` equal to, or a subdomain of, the `dNSName` constraint `base` (RFC5280
+/// section 4.2.1.10)? Matching is case-insensitive and ignores a trailing root dot on
+/// either side; `base` may itself start with a dot (`.example.com`), which RFC5280
+/// also permits and which this treats the same as `example.com`.
+fn dns_name_is_within(name: &str, base: &str) -> bool {
+    let name = name.trim_end_matches('.');
+    let base = base.trim_start_matches('.').trim_end_matches('.');
+    if base.is_empty() {
+        return true;
+    }
+    if name.eq_ignore_ascii_case(base) {
+        return true;
+    }
+    match name.len().checked_sub(base.len() + 1) {
+        Some(offset) => {
+            name.as_bytes()[offset] == b'.' && name[offset + 1..].eq_ignore_ascii_case(base)
+        }
+        None => false,
+    }
+}
+
+/// The `dNSName` and `iPAddress` general names from `cert`'s `subjectAltName`, split by
+/// form. Other `GeneralName` variants are not currently matched against name
+/// constraints (see [`ValidationFailure::NameConstraintsViolated`]).
+fn dns_and_ip_sans(cert: &X509Certificate) -> (Vec<&str>, Vec<IpAddr>) {
+    let names = match cert.tbs_certificate.subject_alternative_names() {
+        Some((_, names)) => names,
+        None => return (Vec::new(), Vec::new()),
+    };
+    let dns_names = names
+        .iter()
+        .filter_map(|n| match n {
+            GeneralName::DnsName(d) => Some(*d),
+            _ => None,
+        })
+        .collect();
+    let ip_addresses = names
+        .iter()
+        .filter_map(|n| match n {
+            GeneralName::IpAddress(ip) => Some(*ip),
+            _ => None,
+        })
+        .collect();
+    (dns_names, ip_addresses)
+}
+
+/// Does `base` (the `base` of a `nameConstraints` subtree) rule out `cert`, whether the
+/// match comes from `cert`'s subject DN (`directoryName` subtrees) or a `dNSName`/
+/// `iPAddress` entry in its `subjectAltName` (`dns_sans`/`ip_sans`)?
+fn subtree_matches(
+    base: &GeneralName,
+    subject: &X509Name,
+    dns_sans: &[&str],
+    ip_sans: &[IpAddr],
+) -> bool {
+    match base {
+        GeneralName::DirectoryName(base) => directory_name_is_within(subject, base),
+        GeneralName::DnsName(base) => dns_sans.iter().any(|name| dns_name_is_within(name, base)),
+        GeneralName::IpAddress(base) => ip_sans.contains(base),
+        _ => false,
+    }
+}
+
+fn check_name_constraints(
+    cert: &X509Certificate,
+    ancestors: &[&X509Certificate],
+) -> Option<ValidationFailure> {
+    let subject = cert.subject();
+    let (dns_sans, ip_sans) = dns_and_ip_sans(cert);
+
+    for ancestor in ancestors {
+        let (_, nc) = match ancestor.tbs_certificate.name_constraints() {
+            Some(nc) => nc,
+            None => continue,
+        };
+        if let Some(excluded) = &nc.excluded_subtrees {
+            if excluded
+                .iter()
+                .any(|subtree| subtree_matches(&subtree.base, subject, &dns_sans, &ip_sans))
+            {
+                return Some(ValidationFailure::NameConstraintsViolated);
+            }
+        }
+        if let Some(permitted) = &nc.permitted_subtrees {
+            // A permitted-subtrees constraint only restricts name forms it actually
+            // lists: a certificate with no directoryName permitted subtree among
+            // `permitted` is unconstrained on its subject DN, even though other name
+            // forms may be constrained by the same extension, and vice versa.
+            let directory_bases: Vec<&X509Name> = permitted
+                .iter()
+                .filter_map(|s| match &s.base {
+                    GeneralName::DirectoryName(base) => Some(base),
+                    _ => None,
+                })
+                .collect();
+            if !directory_bases.is_empty()
+                && !directory_bases
+                    .iter()
+                    .any(|base| directory_name_is_within(subject, base))
+            {
+                return Some(ValidationFailure::NameConstraintsViolated);
+            }
+
+            let dns_bases: Vec<&str> = permitted
+                .iter()
+                .filter_map(|s| match &s.base {
+                    GeneralName::DnsName(base) => Some(*base),
+                    _ => None,
+                })
+                .collect();
+            if !dns_bases.is_empty()
+                && !dns_sans
+                    .iter()
+                    .all(|name| dns_bases.iter().any(|base| dns_name_is_within(name, base)))
+            {
+                return Some(ValidationFailure::NameConstraintsViolated);
+            }
+
+            let ip_bases: Vec<IpAddr> = permitted
+                .iter()
+                .filter_map(|s| match &s.base {
+                    GeneralName::IpAddress(base) => Some(*base),
+                    _ => None,
+                })
+                .collect();
+            if !ip_bases.is_empty() && !ip_sans.iter().all(|ip| ip_bases.contains(ip)) {
+                return Some(ValidationFailure::NameConstraintsViolated);
+            }
+        }
+    }
+    None
+}
+
+fn check_extended_key_usage(
+    leaf: &X509Certificate,
+    ancestors: &[&X509Certificate],
+) -> Option<ValidationFailure> {
+    let (_, leaf_eku) = match leaf.tbs_certificate.extended_key_usage() {
+        Some(eku) => eku,
+        None => return None,
+    };
+    for ancestor in ancestors {
+        let (_, restriction) = match ancestor.tbs_certificate.extended_key_usage() {
+            Some(eku) => eku,
+            None => continue,
+        };
+        if restriction.any {
+            continue;
+        }
+        let purposes = [
+            (leaf_eku.server_auth, restriction.server_auth),
+            (leaf_eku.client_auth, restriction.client_auth),
+            (leaf_eku.code_signing, restriction.code_signing),
+            (leaf_eku.email_protection, restriction.email_protection),
+            (leaf_eku.time_stamping, restriction.time_stamping),
+            (leaf_eku.ocsp_signing, restriction.ocsp_signing),
+        ];
+        if purposes.iter().any(|(wanted, allowed)| *wanted && !*allowed) {
+            return Some(ValidationFailure::ExtendedKeyUsageNotPermitted);
+        }
+    }
+    None
+}
+
+/// Attempt to build and validate a certificate chain per RFC5280 section 6.
+///
+/// `chain` does not need to be in any particular order: for each certificate, the
+/// issuer is looked up by matching `issuer` against the `subject` of every other
+/// certificate in `chain` and every certificate in `anchors`. `time` is the
+/// evaluation time used to check `validity.not_before`/`not_after`.
+///
+/// Every certificate's issuer chain must terminate at a certificate in `anchors`:
+/// a self-signed certificate in `chain` is not, by itself, sufficient to validate
+/// anything underneath it, since `anchors` is the only source of actual trust.
+///
+/// The returned [`ChainValidationResult`] lists, for every certificate, which check
+/// (if any) failed, so callers can distinguish e.g. an expired certificate from a
+/// missing trust anchor.
+pub fn validate_chain(
+    chain: &[X509Certificate],
+    anchors: &[TrustAnchor],
+    time: DateTime<Utc>,
+) -> ChainValidationResult {
+    let results = chain
+        .iter()
+        .enumerate()
+        .map(|(index, cert)| CertificateValidationResult {
+            certificate_index: index,
+            failure: validate_one(cert, chain, anchors, time),
+        })
+        .collect();
+    ChainValidationResult { results }
+}
+
+fn validate_one(
+    cert: &X509Certificate,
+    chain: &[X509Certificate],
+    anchors: &[TrustAnchor],
+    time: DateTime<Utc>,
+) -> Option<ValidationFailure> {
+    let validity = cert.validity();
+    let not_before = validity.not_before.to_datetime();
+    let not_after = validity.not_after.to_datetime();
+    if time < not_before || time > not_after {
+        return Some(ValidationFailure::Expired);
+    }
+
+    let is_self_signed = cert.subject() == cert.issuer();
+    let issuer = if is_self_signed {
+        cert
+    } else {
+        match find_issuer(cert, chain, anchors) {
+            Some(issuer) => issuer,
+            None => return Some(ValidationFailure::NoIssuerFound),
+        }
+    };
+
+    #[cfg(feature = "verify")]
+    {
+        let issuer_pki = &issuer.tbs_certificate.subject_pki;
+        if cert.verify_signature(Some(issuer_pki)).is_err() {
+            return Some(ValidationFailure::SignatureInvalid);
+        }
+    }
+
+    // The path built from `issuer` upward (through `chain`, then into `anchors`) must
+    // actually reach a trust anchor. Without this, a self-signed certificate planted
+    // in `chain` -- or a chain that simply never includes an anchor -- would validate
+    // on its own say-so, defeating the purpose of trust anchors entirely.
+    let ancestors = ancestor_chain(issuer, chain, anchors);
+    if !is_trust_anchor(ancestors.last().expect("ancestor_chain always pushes `issuer`"), anchors) {
+        return Some(ValidationFailure::NoTrustAnchor);
+    }
+
+    // Every certificate's issuer -- whether an intermediate or (for a self-signed
+    // certificate) the certificate itself -- must be a CA asserting keyCertSign, and
+    // must not have a pathLenConstraint smaller than the number of CA certificates
+    // actually subordinate to it. This is keyed on the issuer relationship, not on
+    // position within `chain`, so it applies regardless of input order.
+    match issuer.tbs_certificate.basic_constraints() {
+        Some((_, bc)) if bc.ca => {
+            if let Some(path_len) = bc.path_len_constraint {
+                let depth_below_issuer = if is_self_signed {
+                    // `cert` is its own issuer, so it is not subordinate to itself.
+                    subordinate_ca_depth(cert, chain)
+                } else if is_ca(cert) {
+                    1 + subordinate_ca_depth(cert, chain)
+                } else {
+                    0
+                };
+                if depth_below_issuer > path_len {
+                    return Some(ValidationFailure::PathLenExceeded);
+                }
+            }
+        }
+        _ => return Some(ValidationFailure::NotACa),
+    }
+    if let Some((_, ku)) = issuer.tbs_certificate.key_usage() {
+        if ku.flags & KEY_USAGE_KEY_CERT_SIGN == 0 {
+            return Some(ValidationFailure::KeyUsageMissingKeyCertSign);
+        }
+    }
+
+    if let Some(failure) = check_name_constraints(cert, &ancestors) {
+        return Some(failure);
+    }
+    if certs_issued_by(cert, chain).next().is_none() {
+        // `cert` issues nothing else in `chain`, so treat it as a leaf for the
+        // purposes of extended key usage nesting.
+        if let Some(failure) = check_extended_key_usage(cert, &ancestors) {
+            return Some(failure);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,105 @@
+//! Certificate signature verification
+//!
+//! This module is gated behind the `verify` feature, which pulls in `ring` as the
+//! cryptographic backend. The core parser has no dependency on it, so consumers that
+//! only need to parse certificates do not pay for a crypto implementation.
+
+use der_parser::oid::Oid;
+use ring::signature;
+
+use crate::error::X509Error;
+use crate::objects::*;
+use crate::x509::{SubjectPublicKeyInfo, X509Certificate};
+
+/// Supported signature algorithms for [`X509Certificate::verify_signature`].
+///
+/// Only the algorithm families mandated by RFC5280 / the CA/Browser Forum baseline
+/// requirements are implemented; anything else is reported as
+/// `X509Error::SignatureUnsupportedAlgorithm`.
+fn verification_algorithm(
+    oid: &Oid,
+) -> Result<&'static dyn signature::VerificationAlgorithm, X509Error> {
+    match () {
+        _ if *oid == OID_PKCS1_SHA256WITHRSA => Ok(&signature::RSA_PKCS1_2048_8192_SHA256),
+        _ if *oid == OID_PKCS1_SHA384WITHRSA => Ok(&signature::RSA_PKCS1_2048_8192_SHA384),
+        _ if *oid == OID_PKCS1_SHA512WITHRSA => Ok(&signature::RSA_PKCS1_2048_8192_SHA512),
+        _ if *oid == OID_SIG_ECDSA_WITH_SHA256 => Ok(&signature::ECDSA_P256_SHA256_ASN1),
+        _ if *oid == OID_SIG_ECDSA_WITH_SHA384 => Ok(&signature::ECDSA_P384_SHA384_ASN1),
+        _ => Err(X509Error::SignatureUnsupportedAlgorithm),
+    }
+}
+
+/// The broad key family a signature algorithm or public key algorithm belongs to.
+///
+/// Used only to catch a certificate whose `signatureAlgorithm` was generated for one
+/// key family (e.g. RSA) but is being checked against an issuer public key of another
+/// (e.g. EC) -- a mismatch distinct from the signature algorithm being unimplemented.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum AlgorithmFamily {
+    Rsa,
+    Ecdsa,
+}
+
+fn signature_algorithm_family(oid: &Oid) -> Option<AlgorithmFamily> {
+    match () {
+        _ if *oid == OID_PKCS1_SHA256WITHRSA
+            || *oid == OID_PKCS1_SHA384WITHRSA
+            || *oid == OID_PKCS1_SHA512WITHRSA =>
+        {
+            Some(AlgorithmFamily::Rsa)
+        }
+        _ if *oid == OID_SIG_ECDSA_WITH_SHA256 || *oid == OID_SIG_ECDSA_WITH_SHA384 => {
+            Some(AlgorithmFamily::Ecdsa)
+        }
+        _ => None,
+    }
+}
+
+fn public_key_algorithm_family(oid: &Oid) -> Option<AlgorithmFamily> {
+    match () {
+        _ if *oid == OID_PKCS1_RSAENCRYPTION => Some(AlgorithmFamily::Rsa),
+        _ if *oid == OID_EC_PUBLIC_KEY => Some(AlgorithmFamily::Ecdsa),
+        _ => None,
+    }
+}
+
+impl<'a> X509Certificate<'a> {
+    /// Verify the certificate signature against an issuer's public key.
+    ///
+    /// If `issuer_public_key` is `None`, the certificate's own `subject_pki` is used,
+    /// which allows checking a self-signed certificate's signature against itself.
+    ///
+    /// Returns `X509Error::SignatureAlgorithmMismatch` if the certificate's
+    /// `signatureAlgorithm` and the issuer's public key belong to different key
+    /// families (e.g. an RSA signature checked against an EC key), and
+    /// `X509Error::SignatureUnsupportedAlgorithm` if the signature algorithm itself
+    /// is not one of the algorithms this crate implements.
+    ///
+    /// This only checks the cryptographic signature over the raw TBS certificate
+    /// bytes; it does not check validity dates, key usage, or any other constraint.
+    /// See the `validate` module for full chain validation.
+    pub fn verify_signature(
+        &self,
+        issuer_public_key: Option<&SubjectPublicKeyInfo>,
+    ) -> Result<(), X509Error> {
+        let spki = issuer_public_key.unwrap_or(&self.tbs_certificate.subject_pki);
+        let algorithm = verification_algorithm(&self.signature_algorithm.algorithm)?;
+        if let (Some(sig_family), Some(key_family)) = (
+            signature_algorithm_family(&self.signature_algorithm.algorithm),
+            public_key_algorithm_family(&spki.algorithm.algorithm),
+        ) {
+            if sig_family != key_family {
+                return Err(X509Error::SignatureAlgorithmMismatch);
+            }
+        }
+        let key = signature::UnparsedPublicKey::new(
+            algorithm,
+            spki.subject_public_key.data,
+        );
+        key.verify(
+            self.tbs_certificate.raw,
+            self.signature_value.data,
+        )
+        .map_err(|_| X509Error::SignatureVerificationError)
+    }
+}
@@ -2,21 +2,37 @@
 //!
 //! Based on RFC5280
 //!
+//! This module is usable in `#![no_std]` contexts (e.g. embedded TLS stacks and
+//! attestation code) when the `alloc` feature is enabled instead of `std`: it only
+//! needs a global allocator for `Vec`/`String`/`BTreeMap`, not the rest of `std`.
+//! `Validity::time_to_expiration`, which calls the system clock through `chrono`, is
+//! only available with the `std` feature.
 
+#[cfg(feature = "std")]
 use std::fmt;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
 
 use num_bigint::BigUint;
 
 use crate::error::X509Error;
 use crate::extensions::*;
 use crate::objects::*;
-use chrono::offset::{Local, Utc};
+#[cfg(feature = "std")]
+use chrono::offset::Local;
+use chrono::offset::Utc;
 use chrono::DateTime;
 use data_encoding::HEXUPPER;
 use der_parser::ber::{BerObjectContent, BitStringObject};
-use der_parser::der::DerObject;
+use der_parser::der::{DerObject, Tag};
 use der_parser::oid::Oid;
-use std::collections::HashMap;
 
 #[derive(Debug, PartialEq)]
 pub enum X509Version {
@@ -134,7 +150,7 @@ pub struct TbsCertificate<'a> {
     pub subject_pki: SubjectPublicKeyInfo<'a>,
     pub issuer_uid: Option<UniqueIdentifier<'a>>,
     pub subject_uid: Option<UniqueIdentifier<'a>>,
-    pub extensions: HashMap<Oid<'a>, X509Extension<'a>>,
+    pub extensions: BTreeMap<Oid<'a>, X509Extension<'a>>,
     pub(crate) raw: &'a [u8],
     pub(crate) raw_serial: &'a [u8],
 }
@@ -145,10 +161,60 @@ impl<'a> AsRef<[u8]> for TbsCertificate<'a> {
     }
 }
 
+/// A certificate validity time, preserving which of the two RFC5280 `Time` CHOICE
+/// encodings (`UTCTime` or `GeneralizedTime`) was used.
+///
+/// RFC5280 section 4.1.2.5 requires conforming CAs to encode dates through the year
+/// 2049 as `UTCTime` and dates in 2050 or later as `GeneralizedTime`; keeping the
+/// original variant around (rather than collapsing both to a `DateTime<Utc>`) lets
+/// re-encoding reproduce the same choice instead of guessing from the year alone.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum X509Time {
+    /// `UTCTime`, used for dates before 2050.
+    UtcTime(DateTime<Utc>),
+    /// `GeneralizedTime`, used for dates in 2050 or later (and optionally earlier).
+    GeneralizedTime(DateTime<Utc>),
+}
+
+impl X509Time {
+    /// Get the wrapped date, regardless of which encoding it came from.
+    pub fn to_datetime(&self) -> DateTime<Utc> {
+        match self {
+            X509Time::UtcTime(dt) => *dt,
+            X509Time::GeneralizedTime(dt) => *dt,
+        }
+    }
+
+    /// Get the duration since the Unix epoch represented by this time.
+    ///
+    /// Returns `None` if the time is before the epoch (1970-01-01), which RFC5280
+    /// dates never are in practice but which `DateTime<Utc>` does not rule out.
+    pub fn as_unix_duration(&self) -> Option<core::time::Duration> {
+        self.to_datetime()
+            .timestamp()
+            .try_into()
+            .ok()
+            .map(core::time::Duration::from_secs)
+    }
+
+    /// Build an `X509Time` from a decoded `Time` value and the DER tag it was read
+    /// under, so the variant reflects the actual encoding (`UTCTime` 0x17 vs
+    /// `GeneralizedTime` 0x18) rather than guessing it back from the year. `tag`
+    /// is the tag of the ASN.1 element `dt` was decoded from; any tag other than
+    /// `GeneralizedTime` is treated as `UTCTime`, since that is the only other
+    /// encoding RFC5280 permits here.
+    pub(crate) fn from_tag_and_datetime(tag: Tag, dt: DateTime<Utc>) -> X509Time {
+        match tag {
+            Tag::GeneralizedTime => X509Time::GeneralizedTime(dt),
+            _ => X509Time::UtcTime(dt),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Validity {
-    pub not_before: DateTime<Utc>,
-    pub not_after: DateTime<Utc>,
+    pub not_before: X509Time,
+    pub not_after: X509Time,
 }
 
 impl Validity {
@@ -157,9 +223,12 @@ impl Validity {
     /// If the certificate is not currently valid, then `None` is
     /// returned.  Otherwise, the `Duration` until the certificate
     /// expires is returned.
-    pub fn time_to_expiration(&self) -> Option<std::time::Duration> {
-        let nb = self.not_before;
-        let na = self.not_after;
+    ///
+    /// Requires the `std` feature, since it reads the system clock.
+    #[cfg(feature = "std")]
+    pub fn time_to_expiration(&self) -> Option<core::time::Duration> {
+        let nb = self.not_before.to_datetime();
+        let na = self.not_after.to_datetime();
         let now = Local::now().with_timezone(&nb.timezone());
         if now < nb {
             // Not yet valid...
@@ -176,13 +245,14 @@ impl Validity {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn check_validity_expiration() {
     let mut v = Validity {
-        not_before: Utc::now(),
-        not_after: Utc::now(),
+        not_before: X509Time::UtcTime(Utc::now()),
+        not_after: X509Time::UtcTime(Utc::now()),
     };
     assert_eq!(v.time_to_expiration(), None);
-    v.not_after = v.not_after + chrono::Duration::minutes(1);
+    v.not_after = X509Time::UtcTime(v.not_after.to_datetime() + chrono::Duration::minutes(1));
     assert!(v.time_to_expiration().is_some());
     assert!(v.time_to_expiration().unwrap() <= std::time::Duration::from_secs(60));
     // The following assumes this timing won't take 10 seconds... I
@@ -190,12 +260,25 @@ fn check_validity_expiration() {
     assert!(v.time_to_expiration().unwrap() > std::time::Duration::from_secs(50));
 }
 
+#[test]
+fn x509_time_variant_follows_der_tag() {
+    let dt = Utc::now();
+    assert_eq!(
+        X509Time::from_tag_and_datetime(Tag::UtcTime, dt),
+        X509Time::UtcTime(dt)
+    );
+    assert_eq!(
+        X509Time::from_tag_and_datetime(Tag::GeneralizedTime, dt),
+        X509Time::GeneralizedTime(dt)
+    );
+}
+
 #[derive(Debug, PartialEq)]
 pub struct UniqueIdentifier<'a>(pub BitStringObject<'a>);
 
 impl<'a> TbsCertificate<'a> {
     /// Get a reference to the map of extensions.
-    pub fn extensions(&self) -> &HashMap<Oid, X509Extension> {
+    pub fn extensions(&self) -> &BTreeMap<Oid, X509Extension> {
         &self.extensions
     }
 
@@ -460,7 +543,7 @@ impl<'a> X509Certificate<'a> {
 
     /// Get the certificate extensions.
     #[inline]
-    pub fn extensions(&self) -> &HashMap<Oid, X509Extension> {
+    pub fn extensions(&self) -> &BTreeMap<Oid, X509Extension> {
         self.tbs_certificate.extensions()
     }
 }